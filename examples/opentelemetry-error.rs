@@ -6,7 +6,10 @@ use std::{
     time::{Duration, SystemTime},
 };
 
-use opentelemetry::{global, trace::TracerProvider};
+use opentelemetry::{
+    global,
+    trace::{TraceError, TracerProvider},
+};
 use opentelemetry_sdk::Resource;
 
 use opentelemetry_sdk::{
@@ -58,12 +61,33 @@ fn double_failable_work(fail: bool) -> Result<&'static str, Error> {
 }
 
 fn main() -> Result<(), Box<dyn StdError + Send + Sync + 'static>> {
-    let builder = sdk::trace::TracerProvider::builder().with_simple_exporter(WriterExporter{resource: Resource::default()});
-    let provider = builder.build();
-    let tracer = provider
-        .tracer_builder("opentelemetry-write-exporter")
-        .build();
-    global::set_tracer_provider(provider);
+    // Install a writer-backed pipeline. `new_pipeline` builds the provider,
+    // registers it globally and hands back a tracer plus a guard that shuts the
+    // provider down on drop. Swap `std::io::stdout()` for any `Write` sink.
+    //
+    // The output format is selectable via `WRITER_FORMAT=pretty|compact|json`,
+    // defaulting to the pretty text layout.
+    let builder = WriterExporter::builder().with_writer(std::io::stdout());
+    let builder = match std::env::var("WRITER_FORMAT").as_deref() {
+        Ok("compact") => builder.with_pretty_print(false),
+        Ok("json") => builder.with_json(true),
+        _ => builder.with_pretty_print(true),
+    };
+    // `WRITER_VERBOSITY=basic|normal|detailed` trades console noise for detail,
+    // defaulting to the full detailed dump.
+    let builder = builder.with_verbosity(match std::env::var("WRITER_VERBOSITY").as_deref() {
+        Ok("basic") => Verbosity::Basic,
+        Ok("normal") => Verbosity::Normal,
+        _ => Verbosity::Detailed,
+    });
+    // `WRITER_BACKGROUND=1` moves writing onto a dedicated worker thread so span
+    // end no longer pays the I/O latency on the instrumented thread.
+    let builder = builder.with_background(std::env::var("WRITER_BACKGROUND").is_ok());
+    // In background mode, bound the flush/shutdown that runs when `_guard`
+    // drops, so a hung sink cannot block `main` forever. Foreground writes are
+    // not bounded by this timeout.
+    let builder = builder.with_timeout(Duration::from_secs(5));
+    let (tracer, _guard) = builder.build().new_pipeline();
 
     let opentelemetry = tracing_opentelemetry::layer().with_tracer(tracer);
     tracing_subscriber::registry()
@@ -85,48 +109,439 @@ fn main() -> Result<(), Box<dyn StdError + Send + Sync + 'static>> {
         let _ = double_failable_work(true);
     } // Once this scope is closed, all spans inside are closed as well
 
-    // Shut down the current tracer provider. This will invoke the shutdown
-    // method on all span processors. span processors should export remaining
-    // spans before return.
-    global::shutdown_tracer_provider();
+    // `_guard` is dropped here, which shuts down the current tracer provider.
+    // That invokes the shutdown method on all span processors, so processors
+    // export any remaining spans before return.
 
     Ok(())
 }
 
+/// A [`SpanExporter`] that serializes each span to an arbitrary [`Write`] sink.
+///
+/// Construct one with [`WriterExporter::builder`]; the builder accepts any
+/// writer (a file, a pipe, a `Vec<u8>`, ...) so a custom sink can be dropped in
+/// without re-implementing the `SpanExporter` and `Display` plumbing by hand.
+///
+/// By default `export` writes on the calling thread. With
+/// [`WriterExporterBuilder::with_background`] a dedicated worker thread owns the
+/// writer instead: `export` serializes each span and hands it to a bounded
+/// channel, returning immediately, while the worker drains the channel and
+/// writes. `shutdown` (and drop) close the channel and join the worker so every
+/// queued span is flushed before return; with a timeout set they stop waiting
+/// once it elapses and detach the worker, so spans still queued may be lost.
 #[derive(Debug)]
-struct WriterExporter{
-    // set exporter
-    resource: Resource
+struct WriterExporter<W> {
+    sink: Sink<W>,
+    resource: Resource,
+    format: Format,
+    verbosity: Verbosity,
+    timeout: Option<Duration>,
+}
+
+/// How each [`SpanData`] is rendered to the sink.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// Human-readable, indented multi-line text (the default).
+    Pretty,
+    /// A single line per span.
+    Compact,
+    /// One JSON object per span, newline-delimited, for piping into `jq` or a
+    /// collector.
+    Json,
+}
+
+/// How much of each [`SpanData`] the text formatters render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verbosity {
+    /// Name, status and duration only.
+    Basic,
+    /// Adds start/end timestamps, attributes and events.
+    Normal,
+    /// Adds the trace context ids, span kind, resource and links — the full
+    /// dump (the default).
+    Detailed,
+}
+
+/// A unit of work handed to the background worker.
+#[derive(Debug)]
+enum Message {
+    /// Rendered span text to write.
+    Export(String),
+    /// Flush the sink, then acknowledge on the enclosed channel so a bounded
+    /// `force_flush` can observe that the queue has drained.
+    Flush(crossbeam_channel::Sender<()>),
+}
+
+/// Where a [`WriterExporter`] sends its rendered spans.
+#[derive(Debug)]
+enum Sink<W> {
+    /// The writer is owned by the exporter and written to inline.
+    Foreground(W),
+    /// The writer lives on a background thread fed by a bounded channel.
+    Background {
+        sender: Option<crossbeam_channel::Sender<Message>>,
+        worker: Option<thread::JoinHandle<()>>,
+        /// Signalled once by the worker just before it exits, so `shutdown` can
+        /// wait for drain with a timeout instead of an unbounded `join`.
+        finished: crossbeam_channel::Receiver<()>,
+    },
+}
+
+/// Depth of the bounded background channel. Kept small so a stalled sink exerts
+/// backpressure on the exporter rather than growing an unbounded backlog.
+const BACKGROUND_CHANNEL_CAPACITY: usize = 2_048;
+
+/// Builder for [`WriterExporter`], mirroring the stdout-pipeline pattern.
+#[derive(Debug)]
+struct WriterExporterBuilder<W> {
+    writer: W,
+    format: Format,
+    verbosity: Verbosity,
+    background: bool,
+    timeout: Option<Duration>,
+}
+
+impl WriterExporter<std::io::Stdout> {
+    /// Start building a writer exporter. Defaults to `std::io::stdout()`, the
+    /// pretty indented layout and inline (foreground) writing.
+    fn builder() -> WriterExporterBuilder<std::io::Stdout> {
+        WriterExporterBuilder {
+            writer: std::io::stdout(),
+            format: Format::Pretty,
+            verbosity: Verbosity::Detailed,
+            background: false,
+            timeout: None,
+        }
+    }
+}
+
+impl<W: Write + Send + Debug + 'static> WriterExporterBuilder<W> {
+    /// Use `writer` as the span sink. Any `Write + Send` value works.
+    fn with_writer<W2: Write + Send + Debug + 'static>(
+        self,
+        writer: W2,
+    ) -> WriterExporterBuilder<W2> {
+        WriterExporterBuilder {
+            writer,
+            format: self.format,
+            verbosity: self.verbosity,
+            background: self.background,
+            timeout: self.timeout,
+        }
+    }
+
+    /// Toggle between the human-readable indented layout (`true`, the default)
+    /// and a single-line compact layout (`false`).
+    fn with_pretty_print(mut self, pretty_print: bool) -> Self {
+        self.format = if pretty_print {
+            Format::Pretty
+        } else {
+            Format::Compact
+        };
+        self
+    }
+
+    /// Emit one JSON object per span (`true`) instead of text, so the stream can
+    /// be piped into a collector or `jq`. Passing `false` restores the pretty
+    /// text layout.
+    fn with_json(mut self, json: bool) -> Self {
+        self.format = if json { Format::Json } else { Format::Pretty };
+        self
+    }
+
+    /// Control how much of each span the text formatters render, trading console
+    /// noise for detail. Does not affect the JSON format, which always renders
+    /// the context ids, kind, timestamps, resource, attributes, events and
+    /// links. Defaults to [`Verbosity::Detailed`].
+    fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Move writing off the hot path onto a dedicated worker thread.
+    fn with_background(mut self, background: bool) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Bound how long `force_flush` and `shutdown` wait for pending spans to
+    /// drain. Once the timeout elapses they stop waiting and return a timeout
+    /// error rather than blocking `main` on a hung sink. Only the background
+    /// mode is bounded: in foreground mode the sink is written and flushed
+    /// inline on the calling thread and the timeout has no effect.
+    fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Finish building the exporter, spawning the worker thread if background
+    /// mode was requested.
+    fn build(self) -> WriterExporter<W> {
+        let sink = if self.background {
+            let (sender, receiver) = crossbeam_channel::bounded(BACKGROUND_CHANNEL_CAPACITY);
+            let (finished_tx, finished) = crossbeam_channel::bounded(1);
+            let mut writer = self.writer;
+            let worker = thread::spawn(move || {
+                for message in receiver.iter() {
+                    match message {
+                        Message::Export(line) => {
+                            // A broken sink must not take the worker down;
+                            // ignore the error and keep draining so `shutdown`
+                            // can still make progress.
+                            let _ = writer.write_all(line.as_bytes());
+                        }
+                        Message::Flush(ack) => {
+                            let _ = writer.flush();
+                            let _ = ack.send(());
+                        }
+                    }
+                }
+                let _ = writer.flush();
+                let _ = finished_tx.send(());
+            });
+            Sink::Background {
+                sender: Some(sender),
+                worker: Some(worker),
+                finished,
+            }
+        } else {
+            Sink::Foreground(self.writer)
+        };
+
+        WriterExporter {
+            sink,
+            resource: Resource::default(),
+            format: self.format,
+            verbosity: self.verbosity,
+            timeout: self.timeout,
+        }
+    }
 }
 
-impl SpanExporter for WriterExporter {
+impl<W: Write + Send + Debug + 'static> WriterExporter<W> {
+    /// Install this exporter behind a simple span processor, register the
+    /// resulting provider globally and return the tracer together with a guard
+    /// that shuts the provider down when dropped.
+    fn new_pipeline(self) -> (sdk::trace::Tracer, WriterPipelineGuard) {
+        let provider = sdk::trace::TracerProvider::builder()
+            .with_simple_exporter(self)
+            .build();
+        let tracer = provider
+            .tracer_builder("opentelemetry-write-exporter")
+            .build();
+        let _ = global::set_tracer_provider(provider);
+        (tracer, WriterPipelineGuard(()))
+    }
+}
+
+/// Dropping this guard shuts down the globally installed tracer provider,
+/// flushing any spans still held by its processors.
+#[derive(Debug)]
+struct WriterPipelineGuard(());
+
+impl Drop for WriterPipelineGuard {
+    fn drop(&mut self) {
+        global::shutdown_tracer_provider();
+    }
+}
+
+impl<W: Write + Send + Debug + 'static> WriterExporter<W> {
+    /// Render a batch of spans to the text the sink should emit.
+    fn render(&self, batch: Vec<sdk::export::trace::SpanData>) -> String {
+        use std::fmt::Write as _;
+        let mut out = String::new();
+        for span in batch {
+            let span_data = SpanData {
+                span,
+                resource: self.resource.clone(),
+                format: self.format,
+                verbosity: self.verbosity,
+            };
+            let _ = writeln!(out, "{}", span_data);
+        }
+        if self.format == Format::Pretty {
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl<W: Write + Send + Debug + 'static> SpanExporter for WriterExporter<W> {
     fn export(
         &mut self,
         batch: Vec<sdk::export::trace::SpanData>,
     ) -> futures_util::future::BoxFuture<'static, sdk::export::trace::ExportResult> {
-        let mut writer = std::io::stdout();
-        for span in batch {
-            let span_data = SpanData {span: span, resource: self.resource.clone()};
-            writeln!(writer, "{}", span_data).unwrap();
+        let rendered = self.render(batch);
+        match &mut self.sink {
+            Sink::Foreground(writer) => {
+                writer.write_all(rendered.as_bytes()).unwrap();
+            }
+            Sink::Background { sender, .. } => {
+                // Hand the rendered text to the worker and return at once; no
+                // span is dropped because the bounded channel applies
+                // backpressure instead.
+                if let Some(sender) = sender {
+                    let _ = sender.send(Message::Export(rendered));
+                }
+            }
         }
-        writeln!(writer).unwrap();
 
         Box::pin(async move { ExportResult::Ok(()) })
     }
 
     fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
         self.resource = resource.clone();
-        
+    }
+
+    fn shutdown(&mut self) {
+        // Flush pending spans, then tear the worker down — both bounded by the
+        // configured timeout. The trait method cannot surface the error, so a
+        // timeout is logged instead.
+        if let Err(err) = self.force_flush().and_then(|()| self.drain_and_stop()) {
+            eprintln!("WriterExporter shutdown timed out: {err}");
+        }
+    }
+}
+
+impl<W: Write + Send + Debug + 'static> WriterExporter<W> {
+    /// Flush any spans buffered in the sink, waiting at most the configured
+    /// timeout. Returns a timeout error if the sink did not drain in time.
+    fn force_flush(&mut self) -> ExportResult {
+        match &mut self.sink {
+            Sink::Foreground(writer) => {
+                writer.flush().map_err(|e| TraceError::Other(Box::new(e)))
+            }
+            Sink::Background { sender, .. } => {
+                let sender = match sender {
+                    Some(sender) => sender,
+                    None => return Ok(()),
+                };
+                let (ack_tx, ack_rx) = crossbeam_channel::bounded(1);
+                let flush = Message::Flush(ack_tx);
+                let sent = match self.timeout {
+                    // A full channel behind a hung sink must not block past the timeout.
+                    Some(timeout) => match sender.send_timeout(flush, timeout) {
+                        Ok(()) => true,
+                        Err(crossbeam_channel::SendTimeoutError::Timeout(_)) => {
+                            return Err(TraceError::Other(
+                                format!("export timed out after {timeout:?}").into(),
+                            ));
+                        }
+                        Err(crossbeam_channel::SendTimeoutError::Disconnected(_)) => false,
+                    },
+                    None => sender.send(flush).is_ok(),
+                };
+                if !sent {
+                    // Worker is already gone; nothing left to flush.
+                    return Ok(());
+                }
+                wait_with_timeout(&ack_rx, self.timeout)
+            }
+        }
+    }
+
+    /// Close the channel and wait for the worker to finish, bounded by the
+    /// timeout. On timeout the worker is detached rather than joined.
+    fn drain_and_stop(&mut self) -> ExportResult {
+        match &mut self.sink {
+            Sink::Foreground(writer) => {
+                writer.flush().map_err(|e| TraceError::Other(Box::new(e)))
+            }
+            Sink::Background {
+                sender,
+                worker,
+                finished,
+            } => {
+                drop(sender.take());
+                let result = wait_with_timeout(finished, self.timeout);
+                if result.is_ok() {
+                    if let Some(worker) = worker.take() {
+                        let _ = worker.join();
+                    }
+                }
+                result
+            }
+        }
+    }
+}
+
+/// Block on `signal` until it fires or `timeout` elapses. With no timeout this
+/// waits indefinitely; on expiry it returns a timeout [`TraceError`].
+fn wait_with_timeout(
+    signal: &crossbeam_channel::Receiver<()>,
+    timeout: Option<Duration>,
+) -> ExportResult {
+    match timeout {
+        Some(timeout) => signal
+            .recv_timeout(timeout)
+            .map_err(|_| TraceError::Other(format!("export timed out after {timeout:?}").into())),
+        None => {
+            let _ = signal.recv();
+            Ok(())
+        }
+    }
+}
+
+impl<W> Drop for WriterExporter<W> {
+    fn drop(&mut self) {
+        if let Sink::Background {
+            sender,
+            worker,
+            finished,
+        } = &mut self.sink
+        {
+            // Mirror the bounded `shutdown` wait: close the channel, wait for the
+            // worker to drain against the timeout, and only join once it has
+            // signalled so a hung sink cannot block drop forever.
+            drop(sender.take());
+            if wait_with_timeout(finished, self.timeout).is_ok() {
+                if let Some(worker) = worker.take() {
+                    let _ = worker.join();
+                }
+            }
+        }
     }
 }
 
-struct SpanData{
+struct SpanData {
     span: sdk::export::trace::SpanData,
     resource: Resource,
+    format: Format,
+    verbosity: Verbosity,
 }
+
 impl Display for SpanData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.format {
+            Format::Pretty => self.fmt_pretty(f),
+            Format::Compact => self.fmt_compact(f),
+            Format::Json => self.fmt_json(f),
+        }
+    }
+}
+
+impl SpanData {
+    /// Duration of the span, i.e. `end_time - start_time`.
+    fn duration(&self) -> Duration {
+        self.span
+            .end_time
+            .duration_since(self.span.start_time)
+            .unwrap_or_default()
+    }
+
+    /// The human-readable, indented layout, scoped by [`Verbosity`].
+    fn fmt_pretty(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ctx = &self.span.span_context;
+        let normal = self.verbosity != Verbosity::Basic;
+        let detailed = self.verbosity == Verbosity::Detailed;
+
         writeln!(f, "Span: \"{}\"", self.span.name)?;
+        if detailed {
+            writeln!(f, "- TraceId: {}", ctx.trace_id())?;
+            writeln!(f, "- SpanId: {}", ctx.span_id())?;
+            writeln!(f, "- ParentSpanId: {}", self.span.parent_span_id)?;
+            writeln!(f, "- Kind: {:?}", self.span.span_kind)?;
+        }
         match &self.span.status {
             opentelemetry::trace::Status::Unset => {}
             opentelemetry::trace::Status::Error { description } => {
@@ -135,58 +550,331 @@ impl Display for SpanData {
             }
             opentelemetry::trace::Status::Ok => writeln!(f, "- Status: Ok")?,
         }
-        writeln!(
-            f,
-            "- Start: {}",
-            self.span
-                .start_time
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .expect("start time is before the unix epoch")
-                .as_secs()
-        )?;
-        writeln!(
-            f,
-            "- End: {}",
-            self.span
-                .end_time
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .expect("end time is before the unix epoch")
-                .as_secs()
-        )?;
-        writeln!(f, "- Resource:")?;
-        for (k, v) in self.resource.iter() {
-            writeln!(f, "  - {}: {}", k, v)?;
-        }
-        writeln!(f, "- Attributes:")?;
-        for kv in self.span.attributes.iter() {
-            writeln!(f, "  - {}: {}", kv.key, kv.value)?;
-        }
-
-        writeln!(f, "- Events:")?;
-        for event in self.span.events.iter() {
-            if let Some(error) =
-                event
-                    .attributes
-                    .iter()
-                    .fold(Option::<String>::None, |mut acc, d| {
-                        if let Some(mut acc) = acc.take() {
-                            use std::fmt::Write;
-                            let _ = write!(acc, ", {}={}", d.key, d.value);
-                            Some(acc)
-                        } else {
-                            Some(format!("{} = {}", d.key, d.value))
-                        }
-                    })
-            {
-                writeln!(f, "  - \"{}\" {{{error}}}", event.name)?;
-            } else {
-                writeln!(f, "  - \"{}\"", event.name)?;
+        writeln!(f, "- Duration: {:?}", self.duration())?;
+        if normal {
+            writeln!(f, "- Start: {}", format_rfc3339(self.span.start_time))?;
+            writeln!(f, "- End: {}", format_rfc3339(self.span.end_time))?;
+        }
+        if detailed {
+            writeln!(f, "- Resource:")?;
+            for (k, v) in self.resource.iter() {
+                writeln!(f, "  - {}: {}", k, v)?;
+            }
+        }
+        if normal {
+            writeln!(f, "- Attributes:")?;
+            for kv in self.span.attributes.iter() {
+                writeln!(f, "  - {}: {}", kv.key, kv.value)?;
+            }
+
+            writeln!(f, "- Events:")?;
+            for event in self.span.events.iter() {
+                let timestamp = format_rfc3339(event.timestamp);
+                if let Some(error) =
+                    event
+                        .attributes
+                        .iter()
+                        .fold(Option::<String>::None, |mut acc, d| {
+                            if let Some(mut acc) = acc.take() {
+                                use std::fmt::Write;
+                                let _ = write!(acc, ", {}={}", d.key, d.value);
+                                Some(acc)
+                            } else {
+                                Some(format!("{} = {}", d.key, d.value))
+                            }
+                        })
+                {
+                    writeln!(f, "  - {timestamp} \"{}\" {{{error}}}", event.name)?;
+                } else {
+                    writeln!(f, "  - {timestamp} \"{}\"", event.name)?;
+                }
             }
         }
-        writeln!(f, "- Links:")?;
-        for link in self.span.links.iter() {
-            writeln!(f, "  - {:?}", link)?;
+        if detailed {
+            writeln!(f, "- Links:")?;
+            for link in self.span.links.iter() {
+                writeln!(f, "  - {:?}", link)?;
+            }
         }
         Ok(())
     }
+
+    /// A single-line, grep-friendly rendering of the same span, scoped by
+    /// [`Verbosity`].
+    fn fmt_compact(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ctx = &self.span.span_context;
+        let normal = self.verbosity != Verbosity::Basic;
+        let detailed = self.verbosity == Verbosity::Detailed;
+
+        write!(f, "span=\"{}\"", self.span.name)?;
+        if detailed {
+            write!(
+                f,
+                " trace_id={} span_id={} parent_span_id={} kind={:?}",
+                ctx.trace_id(),
+                ctx.span_id(),
+                self.span.parent_span_id,
+                self.span.span_kind,
+            )?;
+        }
+        match &self.span.status {
+            opentelemetry::trace::Status::Unset => {}
+            opentelemetry::trace::Status::Error { description } => {
+                write!(f, " status=Error error=\"{description}\"")?
+            }
+            opentelemetry::trace::Status::Ok => write!(f, " status=Ok")?,
+        }
+        write!(f, " duration={:?}", self.duration())?;
+        if normal {
+            write!(
+                f,
+                " start={} end={}",
+                format_rfc3339(self.span.start_time),
+                format_rfc3339(self.span.end_time),
+            )?;
+            for kv in self.span.attributes.iter() {
+                write!(f, " {}={}", kv.key, kv.value)?;
+            }
+            for event in self.span.events.iter() {
+                write!(
+                    f,
+                    " event=\"{}\"@{}",
+                    event.name,
+                    format_rfc3339(event.timestamp)
+                )?;
+            }
+        }
+        if detailed {
+            for (k, v) in self.resource.iter() {
+                write!(f, " resource.{}={}", k, v)?;
+            }
+            for link in self.span.links.iter() {
+                write!(
+                    f,
+                    " link={}:{}",
+                    link.span_context.trace_id(),
+                    link.span_context.span_id()
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// One JSON object per span, so the stream can be piped into `jq` or a
+    /// collector. Serialized by hand to avoid pulling in `serde`.
+    fn fmt_json(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ctx = &self.span.span_context;
+        write!(f, "{{\"name\":\"{}\"", json_escape(&self.span.name))?;
+        write!(f, ",\"trace_id\":\"{}\"", ctx.trace_id())?;
+        write!(f, ",\"span_id\":\"{}\"", ctx.span_id())?;
+        write!(f, ",\"parent_span_id\":\"{}\"", self.span.parent_span_id)?;
+        write!(f, ",\"kind\":\"{:?}\"", self.span.span_kind)?;
+        match &self.span.status {
+            opentelemetry::trace::Status::Unset => write!(f, ",\"status\":\"Unset\"")?,
+            opentelemetry::trace::Status::Ok => write!(f, ",\"status\":\"Ok\"")?,
+            opentelemetry::trace::Status::Error { description } => write!(
+                f,
+                ",\"status\":\"Error\",\"error\":\"{}\"",
+                json_escape(description)
+            )?,
+        }
+        write!(f, ",\"start\":\"{}\"", format_rfc3339(self.span.start_time))?;
+        write!(f, ",\"end\":\"{}\"", format_rfc3339(self.span.end_time))?;
+
+        write!(f, ",\"resource\":{{")?;
+        for (i, (k, v)) in self.resource.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            write!(f, "{sep}\"{}\":\"{}\"", json_escape(&k.to_string()), json_escape(&v.to_string()))?;
+        }
+        write!(f, "}}")?;
+
+        write!(f, ",\"attributes\":{{")?;
+        for (i, kv) in self.span.attributes.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            write!(
+                f,
+                "{sep}\"{}\":\"{}\"",
+                json_escape(&kv.key.to_string()),
+                json_escape(&kv.value.to_string())
+            )?;
+        }
+        write!(f, "}}")?;
+
+        write!(f, ",\"events\":[")?;
+        for (i, event) in self.span.events.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            write!(
+                f,
+                "{sep}{{\"name\":\"{}\",\"timestamp\":\"{}\",\"attributes\":{{",
+                json_escape(&event.name),
+                format_rfc3339(event.timestamp)
+            )?;
+            for (j, kv) in event.attributes.iter().enumerate() {
+                let asep = if j == 0 { "" } else { "," };
+                write!(
+                    f,
+                    "{asep}\"{}\":\"{}\"",
+                    json_escape(&kv.key.to_string()),
+                    json_escape(&kv.value.to_string())
+                )?;
+            }
+            write!(f, "}}}}")?;
+        }
+        write!(f, "]")?;
+
+        write!(f, ",\"links\":[")?;
+        for (i, link) in self.span.links.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            write!(
+                f,
+                "{sep}{{\"trace_id\":\"{}\",\"span_id\":\"{}\"}}",
+                link.span_context.trace_id(),
+                link.span_context.span_id()
+            )?;
+        }
+        write!(f, "]")?;
+
+        write!(f, "}}")
+    }
+}
+
+/// Format a [`SystemTime`] as an RFC3339 UTC timestamp with nanosecond
+/// precision, e.g. `2026-07-25T12:34:56.000000789Z`.
+fn format_rfc3339(time: SystemTime) -> String {
+    let dur = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("timestamp is before the unix epoch");
+    let secs = dur.as_secs() as i64;
+    let nanos = dur.subsec_nanos();
+    let days = secs.div_euclid(86_400);
+    let secs_of_day = secs.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+    let hour = secs_of_day / 3_600;
+    let minute = (secs_of_day % 3_600) / 60;
+    let second = secs_of_day % 60;
+    format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}.{nanos:09}Z")
+}
+
+/// Convert a count of days since the Unix epoch into a `(year, month, day)`
+/// triple, following Howard Hinnant's `civil_from_days` algorithm.
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let year = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    (year + if month <= 2 { 1 } else { 0 }, month, day)
+}
+
+/// Escape a string for inclusion in a JSON string literal.
+fn json_escape(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::Status;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::prelude::*;
+
+    /// A [`SpanExporter`] that keeps exported spans in memory instead of writing
+    /// them anywhere.
+    ///
+    /// Clone it to share one buffer across an instrumented run and the test
+    /// code: install a clone behind [`tracing_opentelemetry::layer`], exercise
+    /// the instrumented code, then call `get_finished_spans` to assert on the
+    /// captured span names, status, attributes and events without scraping
+    /// stdout or standing up a collector.
+    #[derive(Debug, Clone, Default)]
+    struct InMemorySpanExporter {
+        spans: Arc<Mutex<Vec<sdk::export::trace::SpanData>>>,
+    }
+
+    impl InMemorySpanExporter {
+        /// Create an exporter backed by an empty, shared span buffer.
+        fn new() -> Self {
+            Self::default()
+        }
+
+        /// Return a snapshot of every span exported so far.
+        fn get_finished_spans(&self) -> Vec<sdk::export::trace::SpanData> {
+            self.spans.lock().unwrap().clone()
+        }
+
+        /// Drop all captured spans, e.g. between test cases.
+        fn reset(&self) {
+            self.spans.lock().unwrap().clear();
+        }
+    }
+
+    impl SpanExporter for InMemorySpanExporter {
+        fn export(
+            &mut self,
+            batch: Vec<sdk::export::trace::SpanData>,
+        ) -> futures_util::future::BoxFuture<'static, sdk::export::trace::ExportResult> {
+            self.spans.lock().unwrap().extend(batch);
+            Box::pin(async move { ExportResult::Ok(()) })
+        }
+    }
+
+    #[test]
+    fn in_memory_exporter_captures_spans_and_events() {
+        let exporter = InMemorySpanExporter::new();
+        let provider = sdk::trace::TracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let tracer = provider.tracer_builder("in-memory-test").build();
+        let subscriber =
+            tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+
+        // The simple processor exports each span as it closes, so the buffer is
+        // populated by the time the scoped subscriber guard is dropped.
+        tracing::subscriber::with_default(subscriber, || {
+            let _ = double_failable_work(true);
+        });
+
+        let spans = exporter.get_finished_spans();
+        let names: Vec<String> = spans.iter().map(|span| span.name.to_string()).collect();
+        assert!(names.iter().any(|name| name == "double_failable_work"));
+        assert!(names.iter().any(|name| name == "expensive_step_1"));
+        assert!(names.iter().any(|name| name == "expensive_step_2"));
+
+        let root = spans
+            .iter()
+            .find(|span| span.name.as_ref() == "double_failable_work")
+            .expect("root span should have been captured");
+        assert!(
+            matches!(root.status, Status::Error { .. }),
+            "failing work should mark the span as errored"
+        );
+        assert!(
+            root.events.iter().any(|event| {
+                event
+                    .attributes
+                    .iter()
+                    .any(|kv| kv.key.as_str() == "error" && kv.value.to_string() == "test")
+            }),
+            "expected an `error = \"test\"` event on the root span"
+        );
+
+        exporter.reset();
+        assert!(exporter.get_finished_spans().is_empty());
+    }
 }